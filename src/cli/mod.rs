@@ -6,7 +6,9 @@ mod raw;
 #[cfg(test)]
 mod tests;
 
+use std::collections::HashMap;
 use std::env;
+use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
 use std::process;
@@ -20,12 +22,198 @@ use crate::context::{log_error, Context, Output, Verbosity};
 use crate::lock::LockMode;
 use crate::util::build;
 
+/// The names of every built-in subcommand, used to suggest a correction when
+/// an unknown subcommand is given.
+const KNOWN_SUBCOMMANDS: &[&str] = &[
+    "init",
+    "add",
+    "edit",
+    "remove",
+    "lock",
+    "source",
+    "sync",
+    "completions",
+    "version",
+];
+
+/// Global options that consume a separate value token, so that value is not
+/// mistaken for the subcommand (e.g. the `always` in `--color always up`).
+const VALUE_OPTIONS: &[&str] = &[
+    "--color",
+    "--config-dir",
+    "--data-dir",
+    "--config-file",
+    "--profile",
+    "--remote",
+];
+
 /// Parse the command line arguments.
 ///
 /// In the event of failure it will print the error message and quit the program
 /// without returning.
 pub fn from_args() -> Opt {
-    Opt::from_raw_opt(RawOpt::parse())
+    let args: Vec<String> = env::args().collect();
+
+    match RawOpt::try_parse_from(&args) {
+        Ok(raw_opt) => Opt::from_raw_opt(raw_opt),
+        Err(err) => {
+            let aliases = load_aliases(&args);
+
+            if let Some((pos, word)) = find_subcommand(&args) {
+                if let Some(expansion) = aliases.get(word) {
+                    let mut expanded = args[..pos].to_vec();
+                    expanded.extend(expansion.split_whitespace().map(String::from));
+                    expanded.extend(args[pos + 1..].iter().cloned());
+                    if let Ok(raw_opt) = RawOpt::try_parse_from(&expanded) {
+                        return Opt::from_raw_opt(raw_opt);
+                    }
+                }
+                if let Some(suggestion) = suggest_subcommand(word, &aliases) {
+                    eprintln!("did you mean `{suggestion}`?");
+                }
+            }
+
+            err.exit()
+        }
+    }
+}
+
+/// Find the index and value of the first positional argument, skipping over
+/// `argv[0]` and any global option together with the value it consumes.
+fn find_subcommand(args: &[String]) -> Option<(usize, &str)> {
+    let mut iter = args.iter().enumerate().skip(1);
+    while let Some((i, arg)) = iter.next() {
+        let name = arg.split('=').next().unwrap_or(arg);
+        if VALUE_OPTIONS.contains(&name) {
+            if !arg.contains('=') {
+                iter.next();
+            }
+            continue;
+        }
+        if arg.starts_with('-') {
+            continue;
+        }
+        return Some((i, arg));
+    }
+    None
+}
+
+/// Read the `[alias]` table from the config file, honoring `--config-dir`/
+/// `--config-file` exactly like [`resolve_paths`] would, so this works
+/// before clap has parsed anything (which may be why we got here).
+fn load_aliases(args: &[String]) -> HashMap<String, String> {
+    alias_config_file(args)
+        .and_then(|config_file| fs::read_to_string(config_file).ok())
+        .and_then(|contents| contents.parse::<toml::Value>().ok())
+        .and_then(|value| {
+            value.get("alias").and_then(toml::Value::as_table).map(|table| {
+                table
+                    .iter()
+                    .filter_map(|(k, v)| v.as_str().map(|v| (k.clone(), v.to_string())))
+                    .collect()
+            })
+        })
+        .unwrap_or_default()
+}
+
+/// Resolve the config file to read `[alias]` from, mirroring the
+/// `(config_dir, config_file)` precedence in [`resolve_paths`] using only a
+/// raw scan of `args`, since clap has not parsed them yet.
+fn alias_config_file(args: &[String]) -> Option<PathBuf> {
+    let config_file = find_value(args, "--config-file").map(PathBuf::from);
+    let config_dir = find_value(args, "--config-dir").map(PathBuf::from);
+
+    match (config_dir, config_file) {
+        (_, Some(file)) => Some(file),
+        (Some(dir), None) => Some(dir.join("plugins.toml")),
+        (None, None) => home::home_dir().map(|home| default_config_dir(&home).join("plugins.toml")),
+    }
+}
+
+/// Find the value passed for `flag` as either `--flag value` or
+/// `--flag=value`.
+fn find_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if let Some(value) = arg.strip_prefix(flag).and_then(|rest| rest.strip_prefix('=')) {
+            return Some(value);
+        }
+        if arg == flag {
+            return iter.next().map(String::as_str);
+        }
+    }
+    None
+}
+
+/// Find the known subcommand or alias closest to `word`, using the
+/// Levenshtein edit distance, as long as it is within 3 edits.
+fn suggest_subcommand(word: &str, aliases: &HashMap<String, String>) -> Option<String> {
+    let candidates = KNOWN_SUBCOMMANDS
+        .iter()
+        .map(|&s| s.to_string())
+        .chain(aliases.keys().cloned());
+    candidates
+        .map(|candidate| {
+            let distance = levenshtein(word, &candidate);
+            (candidate, distance)
+        })
+        .filter(|&(_, distance)| distance <= 3)
+        .min_by_key(|&(_, distance)| distance)
+        .map(|(candidate, _)| candidate)
+}
+
+/// Compute the Levenshtein edit distance between two strings using the
+/// standard dynamic programming solution over a `(m+1)×(n+1)` matrix.
+fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let (m, n) = (a.len(), b.len());
+
+    let mut dp = vec![vec![0; n + 1]; m + 1];
+    for (i, row) in dp.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for j in 0..=n {
+        dp[0][j] = j;
+    }
+    for i in 1..=m {
+        for j in 1..=n {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            dp[i][j] = (dp[i - 1][j] + 1)
+                .min(dp[i][j - 1] + 1)
+                .min(dp[i - 1][j - 1] + cost);
+        }
+    }
+    dp[m][n]
+}
+
+/// Rank a [`Verbosity`] so it can be compared against a minimum threshold.
+fn verbosity_rank(verbosity: &Verbosity) -> u8 {
+    match verbosity {
+        Verbosity::Quiet => 0,
+        Verbosity::Normal => 1,
+        Verbosity::Verbose => 2,
+        Verbosity::Debug => 3,
+        Verbosity::Trace => 4,
+    }
+}
+
+/// Print a diagnostic line when `output` is at `Debug` verbosity or above;
+/// at `Trace` the line is additionally prefixed with a Unix timestamp, so
+/// power users can correlate path resolution with external tools like `git`
+/// without recompiling.
+fn log_debug(output: &Output, message: &str) {
+    if verbosity_rank(&output.verbosity) < verbosity_rank(&Verbosity::Debug) {
+        return;
+    }
+    if verbosity_rank(&output.verbosity) >= verbosity_rank(&Verbosity::Trace) {
+        let since_epoch = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default();
+        eprintln!("[{}.{:03}] {message}", since_epoch.as_secs(), since_epoch.subsec_millis());
+    } else {
+        eprintln!("{message}");
+    }
 }
 
 /// Resolved command line options with defaults set.
@@ -55,6 +243,28 @@ pub enum Command {
     Lock,
     /// Generate and print out the script.
     Source,
+    /// Synchronize the config and lock files with a remote git repository.
+    Sync { mode: SyncMode },
+}
+
+/// A non-mutating `init` output mode that dumps a `plugins.toml` to stdout
+/// instead of writing it to `config_file`, mirroring rustfmt's
+/// `--dump-default-config`/`--dump-minimal-config`.
+enum ConfigDump {
+    /// Dump a fully-commented default config.
+    Default,
+    /// Dump only the non-default keys.
+    Minimal,
+}
+
+/// The direction to synchronize in for [`Command::Sync`].
+#[derive(Debug)]
+pub enum SyncMode {
+    /// Commit and push local changes to the remote.
+    Push,
+    /// Fetch the remote and copy its config and lock files over the local
+    /// ones, backing up the config file first; no merge is done.
+    Pull,
 }
 
 impl Opt {
@@ -68,15 +278,40 @@ impl Opt {
             config_dir,
             config_file,
             profile,
+            remote,
             command,
         } = raw_opt;
 
         let mut lock_mode = None;
+        let interactive = !non_interactive;
 
         let command = match command {
-            RawCommand::Init { shell } => Command::Init { shell },
+            RawCommand::Init {
+                shell,
+                dump_default_config,
+                dump_minimal_config,
+            } => {
+                let dump = match (dump_default_config, dump_minimal_config) {
+                    (false, false) => None,
+                    (true, false) => Some(ConfigDump::Default),
+                    (false, true) => Some(ConfigDump::Minimal),
+                    (true, true) => unreachable!(),
+                };
+                if let Some(dump) = dump {
+                    print!("{}", dump_config(shell, dump));
+                    process::exit(0);
+                }
+                Command::Init { shell }
+            }
             RawCommand::Add(add) => {
-                let (name, plugin) = EditPlugin::from_add(*add);
+                let (name, plugin) = if interactive && add.has_no_flags() {
+                    EditPlugin::from_add_interactive(*add).unwrap_or_else(|err| {
+                        log_error(!color.is_color(), &err);
+                        process::exit(1);
+                    })
+                } else {
+                    EditPlugin::from_add(*add)
+                };
                 Command::Add {
                     name,
                     plugin: Box::new(plugin),
@@ -105,14 +340,24 @@ impl Opt {
                 println!("{} {}", build::CRATE_NAME, build::CRATE_VERBOSE_VERSION);
                 process::exit(0);
             }
+            RawCommand::Sync { push, pull } => Command::Sync {
+                mode: match (push, pull) {
+                    (false, false) | (false, true) => SyncMode::Pull,
+                    (true, false) => SyncMode::Push,
+                    (true, true) => unreachable!(),
+                },
+            },
         };
 
         let verbosity = if quiet {
             Verbosity::Quiet
-        } else if verbose {
-            Verbosity::Verbose
         } else {
-            Verbosity::Normal
+            match verbose {
+                0 => Verbosity::Normal,
+                1 => Verbosity::Verbose,
+                2 => Verbosity::Debug,
+                _ => Verbosity::Trace,
+            }
         };
 
         let output = Output {
@@ -129,21 +374,28 @@ impl Opt {
             }
         };
 
-        let (config_dir, data_dir, config_file) =
-            match resolve_paths(&home, config_dir, data_dir, config_file) {
+        let (config_dir, data_dir, config_file, remote) =
+            match resolve_paths(&home, config_dir, data_dir, config_file, remote) {
                 Ok(paths) => paths,
                 Err(err) => {
                     log_error(output.no_color, &err);
                     process::exit(1);
                 }
             };
-        let lock_file = match profile.as_deref() {
-            Some("") | None => data_dir.join("plugins.lock"),
-            Some(p) => data_dir.join(format!("plugins.{p}.lock")),
+        let profiles = resolve_profiles(profile);
+        let lock_file = if profiles.is_empty() {
+            data_dir.join("plugins.lock")
+        } else {
+            data_dir.join(format!("plugins.{}.lock", profiles_suffix(&profiles)))
         };
         let clone_dir = data_dir.join("repos");
         let download_dir = data_dir.join("downloads");
 
+        log_debug(&output, &format!("config file: `{}`", config_file.display()));
+        log_debug(&output, &format!("data dir: `{}`", data_dir.display()));
+        log_debug(&output, &format!("lock file: `{}`", lock_file.display()));
+        log_debug(&output, &format!("active profiles: {profiles:?}"));
+
         let ctx = Context {
             version: build::CRATE_RELEASE.to_string(),
             home,
@@ -153,7 +405,8 @@ impl Opt {
             lock_file,
             clone_dir,
             download_dir,
-            profile,
+            profiles,
+            remote,
             output,
             interactive: !non_interactive,
             lock_mode,
@@ -163,6 +416,43 @@ impl Opt {
     }
 }
 
+/// Render the `plugins.toml` that `sheldon init --dump-default-config`/
+/// `--dump-minimal-config` writes to stdout for `shell`, instead of creating
+/// `config_file` on disk.
+fn dump_config(shell: Option<Shell>, mode: ConfigDump) -> String {
+    match mode {
+        ConfigDump::Default => {
+            let shell = shell
+                .map(|shell| format!("{shell:?}").to_lowercase())
+                .unwrap_or_else(|| "zsh".to_string());
+            format!(
+                "\
+# The shell that sheldon generates scripts for.
+shell = \"{shell}\"
+
+# A git remote to synchronize this file and the lock file with, used by
+# `sheldon sync --push`/`sheldon sync --pull`. `--remote` overrides this if given.
+# remote = \"git@github.com:user/dotfiles.git\"
+
+# User-defined subcommand aliases, e.g. `sheldon up` for `sheldon lock --update`.
+# [alias]
+# up = \"lock --update\"
+
+# [plugins.example]
+# github = \"owner/repo\"
+# uses = [\"*.plugin.zsh\"]
+# apply = [\"source\"]
+"
+            )
+        }
+        // Only emit non-default keys: a `shell` line is written only if the
+        // user actually gave one, rather than fabricating `zsh` as if chosen.
+        ConfigDump::Minimal => shell
+            .map(|shell| format!("shell = \"{}\"\n", format!("{shell:?}").to_lowercase()))
+            .unwrap_or_default(),
+    }
+}
+
 impl EditPlugin {
     fn from_add(add: Add) -> (String, Self) {
         let Add {
@@ -215,6 +505,68 @@ impl EditPlugin {
             }),
         )
     }
+
+    /// Open `$EDITOR` on a prefilled, commented TOML stub for `name`, then
+    /// parse the edited buffer back into a plugin, giving the user a guided
+    /// way to set `apply`, `uses`, `hooks` and `profiles` without memorizing
+    /// every flag.
+    fn from_add_interactive(add: Add) -> Result<(String, Self)> {
+        let name = add.name.clone();
+        let stub = format!(
+            "\
+# Fill in exactly one plugin source, then uncomment any of the fields
+# below to customize how `{name}` is installed and loaded.
+
+# git = \"https://github.com/owner/repo\"
+# gist = \"579d02802b1cc17baed07753d09f5009\"
+# github = \"owner/repo\"
+# remote = \"https://github.com/owner/repo/raw/branch/{name}.plugin.zsh\"
+# local = \"~/some/local/directory\"
+
+# branch = \"main\"
+# rev = \"ad152ee\"
+# tag = \"v0.1.0\"
+# dir = \"relative/subdirectory\"
+
+# uses = [\"{name}.plugin.zsh\"]
+# apply = [\"source\"]
+# profiles = [\"work\"]
+
+# [hooks]
+# pre = \"echo 'loading {name}'\"
+"
+        );
+
+        let edited = edit::edit(stub).context("failed to open `$EDITOR`")?;
+        let raw: RawPlugin =
+            toml::from_str(&edited).context("failed to parse the edited plugin")?;
+
+        Ok((name, Self::from(raw)))
+    }
+}
+
+impl Add {
+    /// Whether no flags at all were given, in which case `add` falls back to
+    /// an interactive editor prompt. Checking only the source flags let a
+    /// git-reference or config flag passed alongside an absent source (e.g.
+    /// `sheldon add foo --branch main --uses 'foo.zsh'`) be silently dropped
+    /// by opening a blank stub, so every field is checked here instead.
+    fn has_no_flags(&self) -> bool {
+        self.git.is_none()
+            && self.gist.is_none()
+            && self.github.is_none()
+            && self.remote.is_none()
+            && self.local.is_none()
+            && self.proto.is_none()
+            && self.branch.is_none()
+            && self.rev.is_none()
+            && self.tag.is_none()
+            && self.dir.is_none()
+            && self.uses.is_empty()
+            && self.apply.is_empty()
+            && self.profiles.is_empty()
+            && self.hooks.is_none()
+    }
 }
 
 impl LockMode {
@@ -243,7 +595,8 @@ fn resolve_paths(
     config_dir: Option<PathBuf>,
     data_dir: Option<PathBuf>,
     config_file: Option<PathBuf>,
-) -> Result<(PathBuf, PathBuf, PathBuf)> {
+    remote: Option<String>,
+) -> Result<(PathBuf, PathBuf, PathBuf, Option<String>)> {
     let (config_dir, config_file) = match (config_dir, config_file) {
         // If both are set, then use them as is
         (Some(dir), Some(file)) => (dir, file),
@@ -274,8 +627,76 @@ fn resolve_paths(
     };
 
     let data_dir = data_dir.unwrap_or_else(|| default_data_dir(home));
+    let remote = remote.or_else(|| remote_from_config(&config_file));
+
+    Ok((config_dir, data_dir, config_file, remote))
+}
 
-    Ok((config_dir, data_dir, config_file))
+/// Fall back to the `remote` key in `config_file` when `--remote` was not
+/// given on the command line, so a remote configured once in `plugins.toml`
+/// is picked up by `sheldon sync` on every machine without repeating the flag.
+fn remote_from_config(config_file: &Path) -> Option<String> {
+    fs::read_to_string(config_file)
+        .ok()?
+        .parse::<toml::Value>()
+        .ok()?
+        .get("remote")
+        .and_then(toml::Value::as_str)
+        .map(str::to_string)
+}
+
+/// Compose the `--profile` values given on the command line into a single
+/// ordered, de-duplicated list, splitting any comma-separated values.
+///
+/// Plugins tagged with any profile in the resulting set are included, and
+/// profiles listed later take precedence over earlier ones, so setups like
+/// `--profile work --profile laptop` can mix machine- and context-specific
+/// plugin subsets.
+fn resolve_profiles(profile: Vec<String>) -> Vec<String> {
+    let mut profiles = Vec::new();
+    for p in profile.iter().flat_map(|p| p.split(',')) {
+        let p = p.trim();
+        if p.is_empty() {
+            continue;
+        }
+        profiles.retain(|existing| existing != p);
+        profiles.push(p.to_string());
+    }
+    profiles
+}
+
+/// Derive a lock-file suffix from an ordered set of profiles, keeping the
+/// common case readable: `--profile work` still resolves to the familiar
+/// `plugins.work.lock`, and `--profile work --profile laptop` to
+/// `plugins.work-laptop.lock`.
+///
+/// A profile name can itself contain the `-` separator (e.g. `--profile
+/// a-b` vs. `--profile a --profile b`), which would let two distinct
+/// profile sets collide on the same joined name. Only fall back to hashing
+/// the ordered set with FNV-1a in that ambiguous case; `DefaultHasher` is
+/// not used for the fallback because its seed is randomized per process,
+/// which would change the lock-file name on every run.
+fn profiles_suffix(profiles: &[String]) -> String {
+    let joined = profiles.join("-");
+    if joined.split('-').eq(profiles.iter().map(String::as_str)) {
+        joined
+    } else {
+        hash_profiles(profiles)
+    }
+}
+
+fn hash_profiles(profiles: &[String]) -> String {
+    const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+    const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let mut hash = FNV_OFFSET;
+    for profile in profiles {
+        for byte in profile.bytes().chain(std::iter::once(0)) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(FNV_PRIME);
+        }
+    }
+    format!("{hash:016x}")
 }
 
 fn default_config_dir(home: &Path) -> PathBuf {
@@ -0,0 +1,79 @@
+use super::*;
+
+#[test]
+fn levenshtein_identical() {
+    assert_eq!(levenshtein("lock", "lock"), 0);
+}
+
+#[test]
+fn levenshtein_empty() {
+    assert_eq!(levenshtein("", ""), 0);
+    assert_eq!(levenshtein("", "lock"), 4);
+    assert_eq!(levenshtein("lock", ""), 4);
+}
+
+#[test]
+fn levenshtein_within_threshold() {
+    assert_eq!(levenshtein("lcok", "lock"), 2);
+    assert_eq!(levenshtein("sourc", "source"), 1);
+}
+
+#[test]
+fn levenshtein_boundary() {
+    // "xyz" vs "lock" is exactly 4 edits apart, i.e. outside the `<= 3`
+    // threshold used by `suggest_subcommand`.
+    assert_eq!(levenshtein("xyz", "lock"), 4);
+}
+
+#[test]
+fn resolve_profiles_splits_commas() {
+    assert_eq!(
+        resolve_profiles(vec!["work,laptop".to_string()]),
+        vec!["work".to_string(), "laptop".to_string()]
+    );
+}
+
+#[test]
+fn resolve_profiles_trims_whitespace() {
+    assert_eq!(
+        resolve_profiles(vec![" work , laptop ".to_string()]),
+        vec!["work".to_string(), "laptop".to_string()]
+    );
+}
+
+#[test]
+fn resolve_profiles_dedup_keeps_last_occurrence() {
+    assert_eq!(
+        resolve_profiles(vec!["work".to_string(), "laptop".to_string(), "work".to_string()]),
+        vec!["laptop".to_string(), "work".to_string()]
+    );
+}
+
+#[test]
+fn resolve_profiles_empty_input_is_empty() {
+    assert!(resolve_profiles(Vec::new()).is_empty());
+    assert!(resolve_profiles(vec!["".to_string(), ", ,".to_string()]).is_empty());
+}
+
+#[test]
+fn profiles_suffix_single_profile_is_readable() {
+    assert_eq!(profiles_suffix(&["work".to_string()]), "work");
+}
+
+#[test]
+fn profiles_suffix_joins_unambiguous_sets() {
+    assert_eq!(
+        profiles_suffix(&["work".to_string(), "laptop".to_string()]),
+        "work-laptop"
+    );
+}
+
+#[test]
+fn profiles_suffix_distinguishes_equivalent_joins() {
+    // A profile name containing the join separator would otherwise collide
+    // with a distinct multi-profile set that joins to the same string.
+    let a = profiles_suffix(&["a-b".to_string()]);
+    let b = profiles_suffix(&["a".to_string(), "b".to_string()]);
+    assert_ne!(a, b);
+    assert_eq!(b, "a-b");
+}
@@ -0,0 +1,154 @@
+//! Synchronize the config and lock files with a git remote.
+//!
+//! This versions `config_file` and `lock_file` in a small git repository
+//! rooted at `data_dir/sync`, so a user can keep their plugin set in step
+//! across multiple machines with `sheldon sync --push`/`sheldon sync --pull`.
+//! Registered from the crate root as the handler for [`crate::cli::Command::Sync`].
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context as ResultExt, Result};
+use git2::{IndexAddOption, Repository, Signature};
+
+use crate::cli::SyncMode;
+use crate::context::Context;
+
+/// Run `sheldon sync`, pushing local changes to `ctx.remote` or pulling the
+/// remote's files and copying them over the local ones (no merge), depending
+/// on `mode`.
+pub fn sync(ctx: &Context, mode: SyncMode) -> Result<()> {
+    let remote = ctx.remote.as_deref().context(
+        "no remote configured; set `remote` in `plugins.toml` or pass `--remote`",
+    )?;
+    let state_dir = ctx.data_dir.join("sync");
+    let repo = open_or_clone(&state_dir, remote)?;
+    let branch = default_branch(&repo)?;
+
+    match mode {
+        SyncMode::Pull => pull(ctx, &repo, &state_dir, &branch),
+        SyncMode::Push => push(ctx, &repo, &state_dir, &branch),
+    }
+}
+
+/// The branch `HEAD` points at, i.e. the remote's default branch right
+/// after `open_or_clone`. Resolved from the repository itself instead of
+/// assumed, since remotes default to `main`, `master`, or something else
+/// entirely.
+fn default_branch(repo: &Repository) -> Result<String> {
+    let head = repo
+        .head()
+        .context("failed to resolve the sync repository HEAD")?;
+    let shorthand = head
+        .shorthand()
+        .context("sync repository HEAD is not a branch")?;
+    Ok(shorthand.to_string())
+}
+
+/// Open the existing sync repository at `state_dir`, or clone `remote` into
+/// it if this is the first sync on this machine.
+fn open_or_clone(state_dir: &Path, remote: &str) -> Result<Repository> {
+    if state_dir.join(".git").is_dir() {
+        Repository::open(state_dir).context("failed to open the sync repository")
+    } else {
+        fs::create_dir_all(state_dir)
+            .context("failed to create the sync state directory")?;
+        Repository::clone(remote, state_dir).context("failed to clone the sync remote")
+    }
+}
+
+/// Fetch `origin`, back up the existing config file, then copy the synced
+/// config and lock files into place.
+fn pull(ctx: &Context, repo: &Repository, state_dir: &Path, branch: &str) -> Result<()> {
+    repo.find_remote("origin")
+        .context("sync remote `origin` not found")?
+        .fetch(&[branch], None, None)
+        .context("failed to fetch the sync remote")?;
+    reset_to_remote_head(repo)?;
+
+    if ctx.config_file.is_file() {
+        let backup = ctx.config_file.with_extension("toml.bak");
+        fs::copy(&ctx.config_file, &backup)
+            .context("failed to back up the existing config file")?;
+    }
+
+    copy_if_present(&state_dir.join("plugins.toml"), &ctx.config_file)?;
+    copy_if_present(&synced_lock_path(ctx, state_dir), &ctx.lock_file)?;
+    Ok(())
+}
+
+/// Stage the local config and lock files into the sync repository and
+/// commit and push them to `origin`.
+fn push(ctx: &Context, repo: &Repository, state_dir: &Path, branch: &str) -> Result<()> {
+    fs::copy(&ctx.config_file, state_dir.join("plugins.toml"))
+        .context("failed to stage the config file for push")?;
+    if ctx.lock_file.is_file() {
+        fs::copy(&ctx.lock_file, synced_lock_path(ctx, state_dir))
+            .context("failed to stage the lock file for push")?;
+    }
+
+    commit_all(repo, "sheldon sync push")?;
+
+    let refspec = format!("refs/heads/{branch}:refs/heads/{branch}");
+    repo.find_remote("origin")
+        .context("sync remote `origin` not found")?
+        .push(&[&refspec], None)
+        .context("failed to push to the sync remote")?;
+    Ok(())
+}
+
+fn synced_lock_path(ctx: &Context, state_dir: &Path) -> PathBuf {
+    state_dir.join(ctx.lock_file.file_name().unwrap_or_else(|| "plugins.lock".as_ref()))
+}
+
+fn copy_if_present(from: &Path, to: &Path) -> Result<()> {
+    if from.is_file() {
+        fs::copy(from, to)
+            .with_context(|| format!("failed to copy `{}` into place", to.display()))?;
+    }
+    Ok(())
+}
+
+/// Hard-reset the sync repository's working tree to `origin`'s fetched head.
+fn reset_to_remote_head(repo: &Repository) -> Result<()> {
+    let head = repo
+        .find_reference("FETCH_HEAD")
+        .context("failed to find the fetched sync remote head")?
+        .peel_to_commit()
+        .context("failed to resolve the fetched sync remote head")?;
+    repo.reset(head.as_object(), git2::ResetType::Hard, None)
+        .context("failed to reset the sync repository to the remote head")
+}
+
+/// Stage every file in the sync repository and commit them on top of
+/// whatever `HEAD` currently points at, if anything.
+fn commit_all(repo: &Repository, message: &str) -> Result<()> {
+    let mut index = repo.index().context("failed to open the sync repository index")?;
+    index
+        .add_all(["*"], IndexAddOption::DEFAULT, None)
+        .context("failed to stage sync changes")?;
+    index
+        .write()
+        .context("failed to write the sync repository index")?;
+
+    let tree = repo
+        .find_tree(index.write_tree().context("failed to write the sync tree")?)
+        .context("failed to find the sync tree")?;
+    let signature =
+        Signature::now("sheldon", "sheldon@localhost").context("failed to create a commit signature")?;
+    let parents = repo
+        .head()
+        .ok()
+        .and_then(|head| head.peel_to_commit().ok());
+
+    repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        message,
+        &tree,
+        &parents.iter().collect::<Vec<_>>(),
+    )
+    .context("failed to commit synced files")?;
+    Ok(())
+}